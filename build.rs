@@ -17,7 +17,7 @@ fn main() {
     let npm_command = if cfg!(windows) { "npm.cmd" } else { "npm" };
     
     let status = Command::new(npm_command)
-        .args(&["run", "docs:build"])
+        .args(["run", "docs:build"])
         .current_dir("documentation")
         .status();
     
@@ -135,7 +135,7 @@ pub async fn download_excel(
     
     // 编码文件名以支持中文（RFC 5987）
     let encoded_filename = encode(&filename);
-    let ascii_filename = if filename.chars().all(|c| c.is_ascii()) {
+    let ascii_filename = if filename.is_ascii() {
         &filename
     } else {
         "download.xlsx"
@@ -195,7 +195,7 @@ pub async fn download_excel_get(
     let encoded_filename = encode(&filename);
     let content_disposition = format!(
         "attachment; filename=\"{}\"; filename*=UTF-8''{}",
-        if filename.chars().all(|c| c.is_ascii()) { &filename } else { "download.xlsx" },
+        if filename.is_ascii() { &filename } else { "download.xlsx" },
         encoded_filename
     );
     
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use utoipa::ToSchema;
 
 /// Excel DSL 顶层结构
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct ExcelDsl {
     /// 输出文件名
     #[schema(example = "report.xlsx")]
@@ -17,12 +17,38 @@ pub struct ExcelDsl {
     #[serde(default)]
     pub styles: HashMap<String, Style>,
     
+    /// 资源池: 资源 ID -> Base64 编码的图片数据，供图片按 asset 引用
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub assets: HashMap<String, String>,
+    
     /// 工作表集合
     pub sheets: Vec<Worksheet>,
+    
+    /// 定义名称 (命名范围 / 命名常量)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defined_names: Vec<DefinedName>,
+    
+    /// 工作簿保护
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protection: Option<WorkbookProtection>,
 }
 
-/// 文档元数据
+/// 定义名称
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DefinedName {
+    /// 名称，如 "TaxRate"、"SalesData"
+    pub name: String,
+    
+    /// 引用的范围或常量公式，如 "=Sheet1!$A$1:$A$10"、"=0.13"
+    pub refers_to: String,
+    
+    /// 作用域工作表名，缺省为工作簿全局
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// 文档元数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct DocumentProperties {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -32,11 +58,65 @@ pub struct DocumentProperties {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub company: Option<String>,
+    
+    /// 主题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    
+    /// 关键词
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    
+    /// 类别
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    
+    /// 经理
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager: Option<String>,
+    
+    /// 备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<String>,
+    
+    /// 状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    
+    /// 超链接基础地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperlink_base: Option<String>,
+    
+    /// 创建时间：ISO 8601 日期/日期时间字符串或 Unix 时间戳 (秒)，缺省为生成时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<CellValue>,
+    
+    /// 自定义属性
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<CustomProperty>,
 }
 
-/// 样式定义
+/// 自定义文档属性
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CustomProperty {
+    /// 属性名
+    pub name: String,
+    
+    /// 属性类型: text, number, bool, date（缺省按值推断，date 需显式指定）
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub property_type: Option<String>,
+    
+    /// 属性值
+    pub value: CellValue,
+}
+
+/// 样式定义
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct Style {
+    /// 继承的父样式 ID，列表按顺序合并，后者覆盖前者，自身属性最后覆盖
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<StyleRef>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub font: Option<FontStyle>,
     
@@ -51,10 +131,32 @@ pub struct Style {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub protect: Option<ProtectStyle>,
+    
+    /// 数字格式：自定义格式串 (如 "#,##0.00") 或内置名称 (如 "currency", "percent_2dp")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_format: Option<String>,
 }
 
-/// 字体样式
+/// 样式引用：单个样式 ID，或按顺序合并的样式 ID 列表
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum StyleRef {
+    Single(String),
+    List(Vec<String>),
+}
+
+impl StyleRef {
+    /// 按合并顺序返回引用的样式 ID
+    pub fn ids(&self) -> &[String] {
+        match self {
+            StyleRef::Single(id) => std::slice::from_ref(id),
+            StyleRef::List(ids) => ids,
+        }
+    }
+}
+
+/// 字体样式
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct FontStyle {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bold: Option<bool>,
@@ -62,40 +164,161 @@ pub struct FontStyle {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub italic: Option<bool>,
     
+    /// 颜色：Hex ("#FF0000")、颜色名 ("red")、"rgb(255, 0, 0)" 或主题色 "theme(4, 2)"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<f64>,
+    
+    /// 字体名称，如 "Microsoft YaHei"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    /// 下划线: none, single, double, single_accounting, double_accounting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underline: Option<String>,
+    
+    /// 删除线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+    
+    /// 上标/下标: none, superscript, subscript
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    
+    /// 轮廓 (暂不支持)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline: Option<bool>,
+    
+    /// 阴影 (暂不支持)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<bool>,
+    
+    /// 字体族 (如 1 Roman、2 Swiss、3 Modern)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<u8>,
+    
+    /// 字符集 (如 134 GB2312)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charset: Option<u8>,
+    
+    /// 字体方案: major, minor, none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
 }
 
 /// 填充样式
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct FillStyle {
-    pub color: String,
+    /// 纯色背景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    
+    /// 图案: solid, gray125, light_grid, dark_trellis 等
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    
+    /// 图案前景色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg_color: Option<String>,
+    
+    /// 图案背景色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_color: Option<String>,
+    
+    /// 渐变填充 (暂不支持)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gradient: Option<serde_json::Value>,
 }
 
 /// 对齐样式
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct AlignStyle {
-    /// 水平对齐: left, center, right
+    /// 水平对齐: general, left, center, right, fill, justify, center_across, distributed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub h: Option<String>,
     
-    /// 垂直对齐: top, vcenter, bottom
+    /// 垂直对齐: top, vcenter, bottom, vjustify, vdistributed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub v: Option<String>,
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text_wrap: Option<bool>,
+    
+    /// 缩进级别
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indent: Option<u8>,
+    
+    /// 文字旋转角度: -90 到 90，270 表示竖排 (文字堆叠)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<i16>,
+    
+    /// 缩小字体填充
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shrink: Option<bool>,
+    
+    /// 文字方向: context (根据内容), ltr (从左到右), rtl (从右到左)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_order: Option<String>,
 }
 
 /// 边框样式
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+///
+/// 单边设置覆盖 `around` 中对应边的线型与颜色。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct BorderStyle {
-    /// 四周边框线型 (1-13)
+    /// 四周边框线型 (0-13 或线型名称)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub around: Option<BorderLine>,
+    
+    /// 四周边框颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    
+    /// 上边框
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top: Option<BorderSide>,
+    
+    /// 下边框
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<BorderSide>,
+    
+    /// 左边框
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<BorderSide>,
+    
+    /// 右边框
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<BorderSide>,
+    
+    /// 对角线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagonal: Option<BorderSide>,
+    
+    /// 对角线方向: up (左下到右上), down (左上到右下), up_down (交叉)，默认 up
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub around: Option<u8>,
+    pub diagonal_type: Option<String>,
+}
+
+/// 边框线型：编号 (0-13) 或名称 (如 "thin", "hair", "mediumDashDot")
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum BorderLine {
+    Code(u8),
+    Name(String),
+}
+
+/// 单边边框：仅线型，或线型加颜色
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum BorderSide {
+    Line(BorderLine),
+    Options {
+        style: BorderLine,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        color: Option<String>,
+    },
 }
 
 /// 保护样式
@@ -105,15 +328,31 @@ pub struct ProtectStyle {
 }
 
 /// 工作表
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct Worksheet {
     /// 工作表名称
     pub name: String,
     
+    /// 工作表类型: worksheet (默认), chartsheet (图表工作表，仅包含 chart)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub sheet_type: Option<String>,
+    
+    /// 图表工作表中的图表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chart: Option<Chart>,
+    
+    /// 工作表保护
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protection: Option<SheetProtection>,
+    
     /// 单元格集合
     #[serde(default)]
     pub cells: Vec<Cell>,
     
+    /// 范围样式：按顺序叠加，单元格自身样式优先
+    #[serde(default)]
+    pub range_styles: Vec<RangeStyle>,
+    
     /// 合并单元格
     #[serde(default)]
     pub merges: Vec<RangeSpec>,
@@ -133,125 +372,1182 @@ pub struct Worksheet {
     /// 迷你图
     #[serde(default)]
     pub sparklines: Vec<Sparkline>,
-}
-
-/// 单元格
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct Cell {
-    /// 行索引 (0-based)
-    pub r: u32,
     
-    /// 列索引 (0-based)
-    pub c: u16,
+    /// 图表
+    #[serde(default)]
+    pub charts: Vec<Chart>,
     
-    /// 数据类型
-    #[serde(rename = "type")]
-    pub cell_type: CellType,
+    /// 图片
+    #[serde(default)]
+    pub images: Vec<Image>,
     
-    /// 单元格值
-    pub value: CellValue,
+    /// 列设置（宽度、隐藏、默认样式）
+    #[serde(default)]
+    pub columns: Vec<ColumnSetting>,
+    
+    /// 行设置（高度、隐藏、默认样式）
+    #[serde(default)]
+    pub rows: Vec<RowSetting>,
     
-    /// 样式引用
+    /// 自动调整列宽: true，或 {"max_width": 50} 限制最大宽度
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub style: Option<String>,
-}
-
-/// 单元格类型
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-#[serde(rename_all = "lowercase")]
-pub enum CellType {
-    String,
-    Number,
-    Datetime,
-    Formula,
-}
-
-/// 单元格值（支持多种类型）
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-#[serde(untagged)]
-pub enum CellValue {
-    String(String),
-    Number(f64),
-    Bool(bool),
-}
-
-/// 范围描述符（支持 A1 引用和坐标系）
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-#[serde(untagged)]
-pub enum RangeSpec {
-    /// A1 引用格式 (如 "A1:C10")
-    A1(String),
+    pub autofit: Option<Autofit>,
     
-    /// 坐标系格式
-    Coords(RangeCoords),
-}
-
-/// 坐标系范围
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct RangeCoords {
-    pub r1: u32,
-    pub c1: u16,
-    pub r2: u32,
-    pub c2: u16,
+    /// 视图设置（冻结窗格、缩放、网格线、标签颜色等）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view: Option<SheetView>,
+    
+    /// 页面设置与打印选项
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_setup: Option<PageSetup>,
 }
 
-/// 表格定义
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct Table {
-    pub range: RangeSpec,
+/// 工作表保护
+///
+/// 允许的操作缺省沿用 Excel 默认值：仅允许选择锁定/未锁定单元格。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct SheetProtection {
+    /// 取消保护所需的密码（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
     
+    /// 允许选择锁定单元格（默认 true）
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub style: Option<String>,
+    pub select_locked_cells: Option<bool>,
     
-    pub columns: Vec<TableColumn>,
-}
-
-/// 表格列
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct TableColumn {
-    pub header: String,
+    /// 允许选择未锁定单元格（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select_unlocked_cells: Option<bool>,
+    
+    /// 允许设置单元格格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_cells: Option<bool>,
+    
+    /// 允许设置列格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_columns: Option<bool>,
+    
+    /// 允许设置行格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_rows: Option<bool>,
+    
+    /// 允许插入列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_columns: Option<bool>,
+    
+    /// 允许插入行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_rows: Option<bool>,
+    
+    /// 允许插入超链接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_links: Option<bool>,
+    
+    /// 允许删除列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_columns: Option<bool>,
+    
+    /// 允许删除行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_rows: Option<bool>,
+    
+    /// 允许排序
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<bool>,
+    
+    /// 允许使用自动筛选
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autofilter: Option<bool>,
+    
+    /// 允许使用数据透视表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pivot_tables: Option<bool>,
+    
+    /// 允许编辑方案
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edit_scenarios: Option<bool>,
+    
+    /// 允许编辑对象（图片、图表等）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edit_objects: Option<bool>,
 }
 
-/// 数据校验
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct DataValidation {
-    pub range: RangeSpec,
+/// 工作簿保护
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct WorkbookProtection {
+    /// 取消保护所需的密码（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
     
-    /// 校验类型: list, integer, decimal, date, time
-    #[serde(rename = "type")]
-    pub validation_type: String,
+    /// 锁定结构（禁止增删、重命名、移动、隐藏工作表），默认 true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structure: Option<bool>,
     
-    /// 允许的值
-    pub value: serde_json::Value,
+    /// 锁定窗口位置与大小
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub windows: Option<bool>,
 }
 
-/// 条件格式
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct ConditionalFormat {
-    pub range: RangeSpec,
+/// 页面设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PageSetup {
+    /// 纸张方向: portrait, landscape
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<String>,
     
-    /// 条件类型: cell, data_bar, color_scale
-    #[serde(rename = "type")]
-    pub format_type: String,
+    /// 纸张大小代码 (1: Letter, 8: A3, 9: A4, 11: A5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paper_size: Option<u8>,
     
+    /// 页边距（英寸）
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub criteria: Option<String>,
+    pub margins: Option<PageMargins>,
     
+    /// 缩放至指定页数，0 表示该方向不限制
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub value: Option<serde_json::Value>,
+    pub fit_to_pages: Option<FitToPages>,
     
+    /// 打印缩放比例 (10-400)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub style: Option<String>,
-}
-
-/// 迷你图
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct Sparkline {
-    /// 插入位置
-    pub location: LocationSpec,
+    pub scale: Option<u16>,
     
-    /// 数据源范围 (需带表名引用，如 "Sheet1!A1:D1")
-    pub range: String,
+    /// 打印区域
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub print_area: Option<RangeSpec>,
+    
+    /// 每页重复打印的行 (如 0 或 "1:2")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat_rows: Option<RowRef>,
+    
+    /// 每页重复打印的列 (如 0 或 "A:B")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat_columns: Option<ColumnRef>,
+    
+    /// 水平分页符: 在这些行 (0-based) 之前分页
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub horizontal_page_breaks: Vec<u32>,
+    
+    /// 垂直分页符: 在这些列 (0-based) 之前分页
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vertical_page_breaks: Vec<u32>,
+    
+    /// 水平居中
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center_horizontally: Option<bool>,
+    
+    /// 垂直居中
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center_vertically: Option<bool>,
+    
+    /// 打印网格线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub print_gridlines: Option<bool>,
+    
+    /// 打印顺序: down_then_over（默认）, over_then_down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_order: Option<String>,
+    
+    /// 页眉 (支持 &L/&C/&R 分区及 &P 页码、&N 总页数、&D 日期、&G 图片等代码)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    
+    /// 页脚
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    
+    /// 页眉图片 (对应分区需包含 &G)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub header_images: Vec<HeaderFooterImage>,
+    
+    /// 页脚图片 (对应分区需包含 &G)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub footer_images: Vec<HeaderFooterImage>,
+}
+
+/// 页边距（英寸），未指定的使用 Excel 默认值
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PageMargins {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<f64>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<f64>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top: Option<f64>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<f64>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<f64>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<f64>,
+}
+
+/// 缩放至页数
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FitToPages {
+    /// 页宽
+    #[serde(default)]
+    pub width: u16,
+    
+    /// 页高
+    #[serde(default)]
+    pub height: u16,
+}
+
+/// 页眉/页脚图片
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HeaderFooterImage {
+    /// 分区: left, center, right
+    pub position: String,
+    
+    /// Base64 编码的图片数据 (PNG/JPEG/GIF/BMP)，与 asset 二选一
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    
+    /// 引用 assets 中的资源 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+}
+
+/// 工作表视图设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct SheetView {
+    /// 冻结窗格: 该单元格上方的行和左侧的列被冻结 (如 "B2" 冻结首行首列)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_panes: Option<LocationSpec>,
+    
+    /// 拆分窗格位置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_panes: Option<LocationSpec>,
+    
+    /// 缩放比例 (10-400)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<u16>,
+    
+    /// 左上角可见单元格
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_left_cell: Option<LocationSpec>,
+    
+    /// 显示网格线（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gridlines: Option<bool>,
+    
+    /// 显示行号列标（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headings: Option<bool>,
+    
+    /// 从右到左显示
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_to_left: Option<bool>,
+    
+    /// 标签颜色 (如 "#FF0000")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab_color: Option<String>,
+    
+    /// 选中该工作表标签
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected: Option<bool>,
+    
+    /// 打开时显示的活动工作表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+    
+    /// 可见性: visible, hidden, very_hidden（仅能通过 VBA 取消隐藏）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+}
+
+/// 列设置
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ColumnSetting {
+    /// 列: 索引 (0-based)、列字母 (如 "B") 或列字母范围 (如 "B:D")
+    pub col: ColumnRef,
+    
+    /// 列宽（字符单位）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    
+    /// 隐藏列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    
+    /// 列默认样式 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    
+    /// 大纲级别 (0-7)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_level: Option<u8>,
+}
+
+/// 列引用
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ColumnRef {
+    /// 列索引 (0-based)
+    Index(u16),
+    
+    /// 列字母或列字母范围 (如 "B"、"B:D")
+    Letters(String),
+}
+
+/// 行设置
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RowSetting {
+    /// 行: 索引 (0-based)，或 Excel 行号范围 (如 "2:5"，1-based)
+    pub row: RowRef,
+    
+    /// 行高（磅）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    
+    /// 隐藏行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    
+    /// 行默认样式 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    
+    /// 大纲级别 (0-7)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_level: Option<u8>,
+}
+
+/// 行引用
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum RowRef {
+    /// 行索引 (0-based)
+    Index(u32),
+    
+    /// Excel 行号或行号范围 (如 "3"、"2:5")
+    Range(String),
+}
+
+/// 自动调整列宽
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Autofit {
+    /// 开启/关闭
+    Enabled(bool),
+    
+    /// 带最大宽度限制
+    Options(AutofitOptions),
+}
+
+/// 自动调整列宽选项
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AutofitOptions {
+    /// 最大列宽（字符单位，按近似估算的内容宽度截断）
+    pub max_width: f64,
+}
+
+/// 单元格
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct Cell {
+    /// 行索引 (0-based)
+    pub r: u32,
+    
+    /// 列索引 (0-based)
+    pub c: u16,
+    
+    /// 数据类型
+    #[serde(rename = "type")]
+    pub cell_type: CellType,
+    
+    /// 单元格值 (blank 类型省略)
+    #[serde(default)]
+    pub value: CellValue,
+    
+    /// 样式引用：样式 ID，或样式 ID 列表 (如 ["base", "money"]，按顺序合并)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<StyleRef>,
+    
+    /// 数字格式 (自定义格式串或内置名称)，覆盖样式中的数字格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_format: Option<String>,
+    
+    /// 链接显示文本 (仅 url 类型)，缺省时显示链接地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    
+    /// 链接提示文本 (仅 url 类型)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    
+    /// 单元格批注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+    
+    /// 数组公式范围 (仅 formula 类型)，左上角须为单元格自身
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub array_range: Option<RangeSpec>,
+    
+    /// 是否按动态数组公式写入 (仅 formula 类型)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic: Option<bool>,
+    
+    /// 公式缓存结果，供不计算公式的查看器显示 (仅 formula 类型)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<CellValue>,
+}
+
+/// 单元格批注
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct Note {
+    /// 批注内容
+    pub text: String,
+    
+    /// 作者，缺省使用文档属性中的 author
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    
+    /// 是否始终显示 (默认仅鼠标悬停时显示)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+    
+    /// 宽度 (像素)，默认 128
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    
+    /// 高度 (像素)，默认 74
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    
+    /// 背景颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_color: Option<String>,
+}
+
+/// 单元格类型
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CellType {
+    #[default]
+    String,
+    Number,
+    /// 布尔值
+    Boolean,
+    /// 空单元格：不带值，仅应用样式
+    Blank,
+    /// 错误值：#N/A、#DIV/0! 等
+    Error,
+    /// 日期时间：ISO 8601 字符串 (日期/日期时间/时间) 或 Unix 时间戳 (秒)
+    Datetime,
+    Formula,
+    /// 超链接：http(s)://、ftp(s)://、mailto:、file:// 或 internal:Sheet2!A1
+    Url,
+    /// 富文本：value 为 {text, style} 片段数组
+    #[serde(rename = "rich_string")]
+    RichString,
+}
+
+/// 单元格值（支持多种类型）
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum CellValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    RichString(Vec<RichStringSegment>),
+}
+
+/// 范围样式
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RangeStyle {
+    /// 应用范围
+    pub range: RangeSpec,
+    
+    /// 样式引用：样式 ID 或样式 ID 列表；其中的边框只作用于范围外沿
+    pub style: StyleRef,
+}
+
+/// 富文本片段
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RichStringSegment {
+    /// 片段文本
+    pub text: String,
+    
+    /// 样式引用，缺省使用默认字体
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+}
+
+impl Default for CellValue {
+    fn default() -> Self {
+        CellValue::String(String::new())
+    }
+}
+
+/// 范围描述符（支持 A1 引用和坐标系）
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum RangeSpec {
+    /// A1 引用格式 (如 "A1:C10")
+    A1(String),
+    
+    /// 坐标系格式
+    Coords(RangeCoords),
+}
+
+/// 坐标系范围
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RangeCoords {
+    pub r1: u32,
+    pub c1: u16,
+    pub r2: u32,
+    pub c2: u16,
+}
+
+/// 表格定义
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Table {
+    /// 表格范围（包含标题行和汇总行）
+    pub range: RangeSpec,
+    
+    /// 表格样式: TableStyleLight1-21, TableStyleMedium1-28, TableStyleDark1-11, TableStyleNone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    
+    /// 表格名称 (如 "SalesData")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    /// 显示标题行（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_row: Option<bool>,
+    
+    /// 显示汇总行（占用范围的最后一行）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_row: Option<bool>,
+    
+    /// 镶边行（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banded_rows: Option<bool>,
+    
+    /// 镶边列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banded_columns: Option<bool>,
+    
+    /// 突出显示第一列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_column: Option<bool>,
+    
+    /// 突出显示最后一列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_column: Option<bool>,
+    
+    /// 标题行筛选按钮（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autofilter: Option<bool>,
+    
+    #[serde(default)]
+    pub columns: Vec<TableColumn>,
+}
+
+/// 表格列
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct TableColumn {
+    pub header: String,
+    
+    /// 汇总函数: sum, average, count, count_numbers, max, min, std_dev, var，或 "=" 开头的自定义公式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_function: Option<String>,
+    
+    /// 汇总行标签（与 total_function 互斥）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_label: Option<String>,
+    
+    /// 计算列公式 (如 "=[@Price]*[@Qty]")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formula: Option<String>,
+    
+    /// 数据区样式 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    
+    /// 标题样式 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_format: Option<String>,
+}
+
+/// 数据校验
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DataValidation {
+    pub range: RangeSpec,
+    
+    /// 校验类型: list, integer, decimal, date, time, text_length, custom, any
+    #[serde(rename = "type")]
+    pub validation_type: String,
+    
+    /// 允许的值: list 为数组或 "=" 开头的区域/名称引用，custom 为公式，
+    /// 其他类型为比较值或 [下限, 上限]（"=" 开头的字符串视为公式）
+    #[serde(default)]
+    pub value: serde_json::Value,
+    
+    /// 比较运算符: between, not_between, ==, !=, >, >=, <, <=（值为数组时默认 between）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub criteria: Option<String>,
+    
+    /// 忽略空值（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_blank: Option<bool>,
+    
+    /// list 类型显示单元格内下拉箭头（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dropdown: Option<bool>,
+    
+    /// 输入提示标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_title: Option<String>,
+    
+    /// 输入提示信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_message: Option<String>,
+    
+    /// 出错警告标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_title: Option<String>,
+    
+    /// 出错警告信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    
+    /// 出错警告样式: stop, warning, information
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_style: Option<String>,
+}
+
+/// 条件格式
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConditionalFormat {
+    pub range: RangeSpec,
+    
+    /// 条件类型: cell, 2_color_scale, 3_color_scale, data_bar, icon_set, top, bottom,
+    /// average, duplicate, unique, text, date, blanks, no_blanks, errors, no_errors, formula
+    #[serde(rename = "type")]
+    pub format_type: String,
+    
+    /// 条件: cell 为比较运算符 (>, <, >=, <=, ==, !=, between, not_between)，
+    /// average/text/date 为对应规则名，top/bottom 为 "%" 时按百分比
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub criteria: Option<String>,
+    
+    /// 条件值: between/not_between 为 [下限, 上限]，以 "=" 开头的字符串视为公式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    
+    /// 满足条件时停止评估后续规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_if_true: Option<bool>,
+    
+    /// 优先级，数值越小越先评估；未指定的规则按声明顺序排在其后
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    
+    /// 色阶/数据条的最小值端点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<ConditionalFormatPoint>,
+    
+    /// 三色色阶的中间点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mid: Option<ConditionalFormatPoint>,
+    
+    /// 色阶/数据条的最大值端点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<ConditionalFormatPoint>,
+    
+    /// 数据条颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bar_color: Option<String>,
+    
+    /// 数据条使用纯色填充
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bar_solid: Option<bool>,
+    
+    /// 仅显示数据条，隐藏数值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bar_only: Option<bool>,
+    
+    /// 图标集类型 (如 3_arrows, 3_traffic_lights, 5_quadrants)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_type: Option<String>,
+    
+    /// 反转图标顺序
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_icons: Option<bool>,
+    
+    /// 仅显示图标，隐藏数值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icons_only: Option<bool>,
+}
+
+/// 色阶/数据条端点
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConditionalFormatPoint {
+    /// 端点类型: lowest, highest, number, percent, percentile, formula, automatic
+    #[serde(rename = "type")]
+    pub point_type: String,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    
+    /// 端点颜色 (仅色阶)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// 迷你图
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Sparkline {
+    /// 插入位置：单个单元格，或分组迷你图的位置区域 (如 "E1:E10")
+    pub location: SparklineLocation,
+    
+    /// 数据源范围 (需带表名引用，如 "Sheet1!A1:D1"；分组迷你图为二维区域)
+    pub range: String,
+    
+    /// 迷你图类型: line, column, win_loss
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub sparkline_type: Option<String>,
+    
+    /// 预设样式 (1-36)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<u8>,
+    
+    /// 显示数据点标记 (仅折线图)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markers: Option<bool>,
+    
+    /// 突出显示最高点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_point: Option<bool>,
+    
+    /// 突出显示最低点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low_point: Option<bool>,
+    
+    /// 突出显示首点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_point: Option<bool>,
+    
+    /// 突出显示尾点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_point: Option<bool>,
+    
+    /// 突出显示负点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negative_points: Option<bool>,
+    
+    /// 颜色设置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colors: Option<SparklineColors>,
+    
+    /// 显示横坐标轴
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_axis: Option<bool>,
+    
+    /// 纵轴最小值，"group" 表示分组内统一
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<SparklineAxisBound>,
+    
+    /// 纵轴最大值，"group" 表示分组内统一
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<SparklineAxisBound>,
+    
+    /// 从右到左绘制
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_to_left: Option<bool>,
+    
+    /// 日期坐标轴范围 (需带表名引用)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_range: Option<String>,
+    
+    /// 绘制隐藏行列中的数据
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_hidden: Option<bool>,
+    
+    /// 空单元格显示方式: gaps, zero, connect
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empty_cells: Option<String>,
+    
+    /// 线宽 (磅，仅折线图)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_weight: Option<f64>,
+}
+
+/// 迷你图位置
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum SparklineLocation {
+    /// A1 引用：单元格 (如 "E1") 或区域 (如 "E1:E10")
+    A1(String),
+    
+    /// 单元格坐标
+    Cell(LocationCoords),
+    
+    /// 区域坐标
+    Range(RangeCoords),
+}
+
+/// 迷你图颜色
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SparklineColors {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negative: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markers: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low: Option<String>,
+}
+
+/// 迷你图纵轴边界
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum SparklineAxisBound {
+    /// 自定义数值
+    Value(f64),
+    
+    /// "group": 分组内统一
+    Group(String),
+}
+
+/// 图表
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Chart {
+    /// 图表类型: column, bar, line, pie, doughnut, scatter, area, radar, stock
+    #[serde(rename = "type")]
+    pub chart_type: String,
+    
+    /// 子类型: stacked, percent_stacked (column/bar/line/area)；
+    /// straight, straight_with_markers, smooth, smooth_with_markers (scatter)；
+    /// with_markers, filled (radar)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+    
+    /// 插入位置（图表左上角所在单元格），图表工作表中可省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<LocationSpec>,
+    
+    /// 宽度（像素，默认 480）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    
+    /// 高度（像素，默认 288）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    
+    /// 相对单元格的水平偏移（像素）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_offset: Option<u32>,
+    
+    /// 相对单元格的垂直偏移（像素）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_offset: Option<u32>,
+    
+    /// 标题: 文本，或 "=" 开头的单元格引用 (如 "=Sheet1!A1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    
+    /// 预设样式 (1-48)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<u8>,
+    
+    /// 数据系列
+    pub series: Vec<ChartSeries>,
+    
+    /// 横坐标轴
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_axis: Option<ChartAxis>,
+    
+    /// 纵坐标轴
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_axis: Option<ChartAxis>,
+    
+    /// 次横坐标轴
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x2_axis: Option<ChartAxis>,
+    
+    /// 次纵坐标轴（用于 secondary_axis 系列）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y2_axis: Option<ChartAxis>,
+    
+    /// 图例
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legend: Option<ChartLegend>,
+    
+    /// 组合的次图表 (如柱形图 + 折线图)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combine: Option<CombinedChart>,
+}
+
+/// 组合图表中的次图表，共用主图表的位置、坐标轴设置和图例
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CombinedChart {
+    /// 图表类型，同 Chart.type
+    #[serde(rename = "type")]
+    pub chart_type: String,
+    
+    /// 子类型，同 Chart.subtype
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+    
+    /// 数据系列（secondary_axis 的系列使用主图表的 y2_axis）
+    pub series: Vec<ChartSeries>,
+}
+
+/// 图表数据系列
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ChartSeries {
+    /// 系列名称: 文本，或 "=" 开头的单元格引用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    /// 分类范围 (需带表名，如 "Sheet1!A2:A10")；散点图为 X 值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<String>,
+    
+    /// 数值范围 (需带表名，如 "Sheet1!B2:B10")
+    pub values: String,
+    
+    /// 系列颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    
+    /// 绘制在次坐标轴上
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_axis: Option<bool>,
+    
+    /// 平滑线（折线图、散点图）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smooth: Option<bool>,
+    
+    /// 数据标签
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_labels: Option<ChartDataLabels>,
+    
+    /// 趋势线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trendline: Option<ChartTrendline>,
+    
+    /// Y 方向误差线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_bars: Option<ChartErrorBars>,
+}
+
+/// 图表坐标轴
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ChartAxis {
+    /// 坐标轴标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    
+    /// 主刻度单位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub major_unit: Option<f64>,
+    
+    /// 次刻度单位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minor_unit: Option<f64>,
+    
+    /// 刻度标签数字格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_format: Option<String>,
+    
+    /// 主网格线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub major_gridlines: Option<bool>,
+    
+    /// 次网格线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minor_gridlines: Option<bool>,
+    
+    /// 逆序刻度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,
+    
+    /// 对数刻度底数 (>= 2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_base: Option<u16>,
+    
+    /// 隐藏坐标轴
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+}
+
+/// 图表图例
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ChartLegend {
+    /// 位置: right, left, top, bottom, top_right
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    
+    /// 隐藏图例
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    
+    /// 图例与绘图区重叠
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlay: Option<bool>,
+}
+
+/// 图表数据标签（未指定显示项时显示数值）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ChartDataLabels {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_value: Option<bool>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_category: Option<bool>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_series_name: Option<bool>,
+    
+    /// 显示百分比（饼图、圆环图）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_percentage: Option<bool>,
+    
+    /// 显示引导线（饼图）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_leader_lines: Option<bool>,
+    
+    /// 位置: center, left, right, above, below, inside_base, inside_end, outside_end, best_fit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    
+    /// 数字格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_format: Option<String>,
+}
+
+/// 图表趋势线
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChartTrendline {
+    /// 类型: linear, exponential, logarithmic, polynomial, power, moving_average
+    #[serde(rename = "type")]
+    pub trendline_type: String,
+    
+    /// 多项式阶数 (polynomial) 或移动平均周期 (moving_average)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<u8>,
+    
+    /// 趋势线名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    /// 向前预测周期
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward_period: Option<f64>,
+    
+    /// 向后预测周期
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backward_period: Option<f64>,
+    
+    /// 显示公式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_equation: Option<bool>,
+    
+    /// 显示 R 平方值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_r_squared: Option<bool>,
+    
+    /// 截距
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intercept: Option<f64>,
+}
+
+/// 图表误差线
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChartErrorBars {
+    /// 类型: fixed, percentage, standard_deviation, standard_error
+    #[serde(rename = "type")]
+    pub error_type: String,
+    
+    /// 误差量（standard_error 以外的类型必填）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    
+    /// 方向: both (默认), plus, minus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    
+    /// 显示末端线帽（默认 true）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_cap: Option<bool>,
+}
+
+/// 图片
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Image {
+    /// Base64 编码的图片数据 (PNG/JPEG/GIF/BMP，可带 data URI 前缀)，与 asset 二选一
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    
+    /// 引用 assets 中的资源 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    
+    /// 插入位置（图片左上角所在单元格）
+    pub location: LocationSpec,
+    
+    /// 水平缩放比例 (1.0 为原始大小)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale_width: Option<f64>,
+    
+    /// 垂直缩放比例 (1.0 为原始大小)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale_height: Option<f64>,
+    
+    /// 相对单元格的水平偏移（像素）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_offset: Option<u32>,
+    
+    /// 相对单元格的垂直偏移（像素）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_offset: Option<u32>,
+    
+    /// 替代文本（辅助功能）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_text: Option<String>,
+    
+    /// 点击图片打开的链接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    
+    /// 定位方式: move_and_size (默认，随单元格移动和调整大小), move (仅移动), none (固定)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positioning: Option<String>,
+    
+    /// 嵌入单元格（作为单元格内容，随单元格缩放；忽略缩放、偏移和定位方式）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embed: Option<bool>,
 }
 
 /// 位置描述符
@@ -30,24 +30,4 @@ impl<T> ApiResponse<T> {
             success: true,
         }
     }
-    
-    /// 成功响应（无数据）
-    pub fn success_without_data() -> ApiResponse<()> {
-        ApiResponse {
-            code: 0,
-            message: "success".to_string(),
-            data: None,
-            success: true,
-        }
-    }
-    
-    /// 失败响应
-    pub fn error(code: i32, message: impl Into<String>) -> ApiResponse<()> {
-        ApiResponse {
-            code,
-            message: message.into(),
-            data: None,
-            success: false,
-        }
-    }
 }
@@ -25,23 +25,57 @@ use crate::models::*;
             ApiResponse<StorageStatusResponse>,
             ExcelDsl,
             DocumentProperties,
+            CustomProperty,
+            DefinedName,
             Style,
+            StyleRef,
             FontStyle,
             FillStyle,
             AlignStyle,
             BorderStyle,
+            BorderLine,
+            BorderSide,
             ProtectStyle,
             Worksheet,
             Cell,
+            RangeStyle,
             CellType,
             CellValue,
+            RichStringSegment,
+            Note,
             RangeSpec,
             RangeCoords,
             Table,
             TableColumn,
             DataValidation,
             ConditionalFormat,
+            ConditionalFormatPoint,
             Sparkline,
+            SparklineLocation,
+            SparklineColors,
+            SparklineAxisBound,
+            Chart,
+            CombinedChart,
+            Image,
+            ChartSeries,
+            ChartAxis,
+            ChartLegend,
+            ChartDataLabels,
+            ChartTrendline,
+            ChartErrorBars,
+            ColumnSetting,
+            ColumnRef,
+            RowSetting,
+            RowRef,
+            Autofit,
+            AutofitOptions,
+            SheetView,
+            SheetProtection,
+            WorkbookProtection,
+            PageSetup,
+            PageMargins,
+            FitToPages,
+            HeaderFooterImage,
             LocationSpec,
             LocationCoords,
             AsyncGenerateResponse,
@@ -16,7 +16,6 @@ pub struct FileStorage {
 
 #[derive(Clone)]
 struct StoredFile {
-    file_id: String,
     filename: String,
     data: Vec<u8>,
     created_at: std::time::Instant,
@@ -53,7 +52,6 @@ impl FileStorage {
         tracing::info!("[存储] 开始存储文件 - file_id: {}, filename: {}, size: {} bytes", file_id, filename, data.len());
         
         let stored = StoredFile {
-            file_id: file_id.clone(),
             filename: filename.clone(),
             data: data.clone(),
             created_at: std::time::Instant::now(),
@@ -219,7 +217,6 @@ impl FileStorage {
                                     // 如果文件未过期，加载到内存
                                     if age <= self.max_age_seconds {
                                         let stored = StoredFile {
-                                            file_id: metadata.file_id.clone(),
                                             filename: metadata.filename.clone(),
                                             data,
                                             created_at: std::time::Instant::now() - std::time::Duration::from_secs(age),